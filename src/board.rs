@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use crate::fen::{Piece, PieceColour};
+
+/// A square on the board, numbered `rank * 8 + file` with `a1 = 0` and
+/// `h8 = 63`, matching the convention used by the bitboards in [`Board`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Square(pub u8);
+
+impl Square {
+    pub fn new(rank: u8, file: u8) -> Self {
+        Square(rank * 8 + file)
+    }
+
+    pub fn rank(self) -> u8 {
+        self.0 / 8
+    }
+
+    pub fn file(self) -> u8 {
+        self.0 % 8
+    }
+
+    /// Parses a square in algebraic notation, e.g. `"e4"`.
+    pub fn from_algebraic(square: &str) -> Option<Self> {
+        let mut chars = square.chars();
+        let file = chars.next()?;
+        let rank = chars.next()?;
+        if chars.next().is_some() {
+            return None;
+        }
+
+        let file_index = ('a'..='h').position(|f| f == file)? as u8;
+        let rank_number = rank.to_digit(10)?;
+        if !(1..=8).contains(&rank_number) {
+            return None;
+        }
+
+        Some(Square::new(rank_number as u8 - 1, file_index))
+    }
+
+    /// Renders this square in algebraic notation, e.g. `"e4"`.
+    pub fn to_algebraic(self) -> String {
+        let file = (b'a' + self.file()) as char;
+        format!("{}{}", file, self.rank() + 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+const ALL_KINDS: [PieceKind; 6] = [
+    PieceKind::Pawn,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Rook,
+    PieceKind::Queen,
+    PieceKind::King,
+];
+
+/// A bitboard-backed board: each of the twelve piece kind/colour
+/// combinations is a `u64` with bit `square.0` set when that piece occupies
+/// the square, plus running occupancy masks per colour. This avoids
+/// allocating and hashing a `String` per square lookup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    pieces: [u64; 12],
+    white_occupancy: u64,
+    black_occupancy: u64,
+}
+
+impl Board {
+    pub fn empty() -> Self {
+        Board {
+            pieces: [0; 12],
+            white_occupancy: 0,
+            black_occupancy: 0,
+        }
+    }
+
+    fn index(kind: PieceKind, colour: PieceColour) -> usize {
+        let colour_index = match colour {
+            PieceColour::White => 0,
+            PieceColour::Black => 1,
+        };
+        kind as usize * 2 + colour_index
+    }
+
+    pub fn set(&mut self, square: Square, piece: Piece) {
+        self.clear(square);
+
+        let (kind, colour) = match piece {
+            Piece::Empty => return,
+            Piece::Pawn(colour) => (PieceKind::Pawn, colour),
+            Piece::Knight(colour) => (PieceKind::Knight, colour),
+            Piece::Bishop(colour) => (PieceKind::Bishop, colour),
+            Piece::Rook(colour) => (PieceKind::Rook, colour),
+            Piece::Queen(colour) => (PieceKind::Queen, colour),
+            Piece::King(colour) => (PieceKind::King, colour),
+        };
+
+        let bit = 1u64 << square.0;
+        self.pieces[Self::index(kind, colour)] |= bit;
+        match colour {
+            PieceColour::White => self.white_occupancy |= bit,
+            PieceColour::Black => self.black_occupancy |= bit,
+        }
+    }
+
+    pub fn clear(&mut self, square: Square) {
+        let mask = !(1u64 << square.0);
+        for bitboard in &mut self.pieces {
+            *bitboard &= mask;
+        }
+        self.white_occupancy &= mask;
+        self.black_occupancy &= mask;
+    }
+
+    pub fn piece_at(&self, square: Square) -> Piece {
+        let bit = 1u64 << square.0;
+        let colour = if self.white_occupancy & bit != 0 {
+            PieceColour::White
+        } else if self.black_occupancy & bit != 0 {
+            PieceColour::Black
+        } else {
+            return Piece::Empty;
+        };
+
+        for kind in ALL_KINDS {
+            if self.pieces[Self::index(kind, colour)] & bit == 0 {
+                continue;
+            }
+
+            return match kind {
+                PieceKind::Pawn => Piece::Pawn(colour),
+                PieceKind::Knight => Piece::Knight(colour),
+                PieceKind::Bishop => Piece::Bishop(colour),
+                PieceKind::Rook => Piece::Rook(colour),
+                PieceKind::Queen => Piece::Queen(colour),
+                PieceKind::King => Piece::King(colour),
+            };
+        }
+
+        Piece::Empty
+    }
+
+    pub fn occupancy(&self) -> u64 {
+        self.white_occupancy | self.black_occupancy
+    }
+
+    pub fn occupancy_for(&self, colour: PieceColour) -> u64 {
+        match colour {
+            PieceColour::White => self.white_occupancy,
+            PieceColour::Black => self.black_occupancy,
+        }
+    }
+
+    pub fn pawns(&self, colour: PieceColour) -> u64 {
+        self.pieces[Self::index(PieceKind::Pawn, colour)]
+    }
+
+    pub fn knights(&self, colour: PieceColour) -> u64 {
+        self.pieces[Self::index(PieceKind::Knight, colour)]
+    }
+
+    pub fn bishops(&self, colour: PieceColour) -> u64 {
+        self.pieces[Self::index(PieceKind::Bishop, colour)]
+    }
+
+    pub fn rooks(&self, colour: PieceColour) -> u64 {
+        self.pieces[Self::index(PieceKind::Rook, colour)]
+    }
+
+    pub fn queens(&self, colour: PieceColour) -> u64 {
+        self.pieces[Self::index(PieceKind::Queen, colour)]
+    }
+
+    pub fn kings(&self, colour: PieceColour) -> u64 {
+        self.pieces[Self::index(PieceKind::King, colour)]
+    }
+
+    /// The square of `colour`'s king, or `None` if it has none (or more
+    /// than one - callers that need to tell the two apart should check
+    /// `kings(colour).count_ones()` themselves).
+    pub fn king_square(&self, colour: PieceColour) -> Option<Square> {
+        let kings = self.kings(colour);
+        if kings.count_ones() != 1 {
+            None
+        } else {
+            Some(Square(kings.trailing_zeros() as u8))
+        }
+    }
+
+    /// Whether any `by`-coloured piece attacks `square`, used both to keep
+    /// a king from castling or moving through check and to tell whether a
+    /// king is in check after a move.
+    pub fn is_attacked(&self, square: Square, by: PieceColour) -> bool {
+        let occupancy = self.occupancy();
+
+        if pawn_attacks(square, by.opposite()) & self.pawns(by) != 0 {
+            return true;
+        }
+        if knight_attacks(square) & self.knights(by) != 0 {
+            return true;
+        }
+        if king_attacks(square) & self.kings(by) != 0 {
+            return true;
+        }
+        if bishop_attacks(square, occupancy) & (self.bishops(by) | self.queens(by)) != 0 {
+            return true;
+        }
+        if rook_attacks(square, occupancy) & (self.rooks(by) | self.queens(by)) != 0 {
+            return true;
+        }
+
+        false
+    }
+
+    /// Converts back to the `square -> Piece` map the crate used before the
+    /// bitboard representation, for callers that still want it.
+    pub fn to_map(&self) -> HashMap<String, Piece> {
+        let mut map = HashMap::new();
+        for index in 0..64u8 {
+            let square = Square(index);
+            let piece = self.piece_at(square);
+            if !matches!(piece, Piece::Empty) {
+                map.insert(square.to_algebraic(), piece);
+            }
+        }
+        map
+    }
+}
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+fn offset_attacks(square: Square, offsets: &[(i8, i8)]) -> u64 {
+    let rank = square.rank() as i8;
+    let file = square.file() as i8;
+
+    let mut attacks = 0u64;
+    for &(dr, df) in offsets {
+        let target_rank = rank + dr;
+        let target_file = file + df;
+        if (0..8).contains(&target_rank) && (0..8).contains(&target_file) {
+            attacks |= 1u64 << Square::new(target_rank as u8, target_file as u8).0;
+        }
+    }
+
+    attacks
+}
+
+/// Walks each direction one square at a time until hitting the edge of the
+/// board or a blocking piece (inclusive of that blocker, so captures show
+/// up - the caller masks off their own pieces afterwards).
+fn ray_attacks(square: Square, occupancy: u64, directions: &[(i8, i8)]) -> u64 {
+    let mut attacks = 0u64;
+
+    for &(dr, df) in directions {
+        let mut rank = square.rank() as i8;
+        let mut file = square.file() as i8;
+        loop {
+            rank += dr;
+            file += df;
+            if !(0..8).contains(&rank) || !(0..8).contains(&file) {
+                break;
+            }
+
+            let bit = 1u64 << Square::new(rank as u8, file as u8).0;
+            attacks |= bit;
+            if occupancy & bit != 0 {
+                break;
+            }
+        }
+    }
+
+    attacks
+}
+
+pub fn knight_attacks(square: Square) -> u64 {
+    offset_attacks(square, &KNIGHT_OFFSETS)
+}
+
+pub fn king_attacks(square: Square) -> u64 {
+    offset_attacks(square, &KING_OFFSETS)
+}
+
+pub fn bishop_attacks(square: Square, occupancy: u64) -> u64 {
+    ray_attacks(square, occupancy, &BISHOP_DIRECTIONS)
+}
+
+pub fn rook_attacks(square: Square, occupancy: u64) -> u64 {
+    ray_attacks(square, occupancy, &ROOK_DIRECTIONS)
+}
+
+/// Squares a `colour` pawn standing on `square` attacks (diagonally
+/// forward only - pushes are not "attacks").
+pub fn pawn_attacks(square: Square, colour: PieceColour) -> u64 {
+    let forward: i8 = match colour {
+        PieceColour::White => 1,
+        PieceColour::Black => -1,
+    };
+
+    let rank = square.rank() as i8;
+    let file = square.file() as i8;
+
+    let mut attacks = 0u64;
+    for df in [-1, 1] {
+        let target_rank = rank + forward;
+        let target_file = file + df;
+        if (0..8).contains(&target_rank) && (0..8).contains(&target_file) {
+            attacks |= 1u64 << Square::new(target_rank as u8, target_file as u8).0;
+        }
+    }
+
+    attacks
+}
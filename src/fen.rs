@@ -2,6 +2,8 @@ use anyhow::Result;
 use std::collections::HashMap;
 use thiserror::Error;
 
+use crate::board::{Board, Square};
+
 #[derive(Error, Debug)]
 pub enum FENParseError {
     #[error("Invalid piece {invalid_piece:?}")]
@@ -10,20 +12,63 @@ pub enum FENParseError {
     #[error("Invalid number of sections in FEN statement, expected 6, found {count:?}")]
     InvalidSectionCount { count: usize },
 
+    #[error("Invalid number of ranks in piece placement, expected 8, found {count:?}")]
+    InvalidRankCount { count: usize },
+
+    #[error("Rank {rank:?} does not expand to exactly 8 files")]
+    InvalidRankLength { rank: String },
+
     #[error("Incomplete board state - duplicate square inserted during FEN parse")]
-    DuplicateSquare
+    DuplicateSquare,
+
+    #[error("Invalid side to move {invalid_side:?}, expected \"w\" or \"b\"")]
+    InvalidSideToMove { invalid_side: String },
+
+    #[error("Invalid castling rights {invalid_rights:?}")]
+    InvalidCastlingRights { invalid_rights: String },
+
+    #[error("Invalid clock value {invalid_clock:?}")]
+    InvalidClock { invalid_clock: String },
+}
+
+/// A position that parsed successfully but describes a board that could
+/// never arise from a legal game.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum InvalidError {
+    #[error("Expected exactly one {colour:?} king, found {count}")]
+    WrongKingCount { colour: PieceColour, count: usize },
+
+    #[error("Pawns cannot stand on the back rank")]
+    PawnOnBackRank,
+
+    #[error("The two kings are on adjacent squares")]
+    NeighbouringKings,
+
+    #[error("Castling rights are inconsistent with the board: {reason}")]
+    InvalidCastlingRights { reason: String },
+
+    #[error("En passant target is not valid: {reason}")]
+    InvalidEnPassant { reason: String },
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PieceColour {
     White,
     Black,
 }
 
-#[derive(Clone, Copy, Debug)]
+impl PieceColour {
+    pub fn opposite(self) -> Self {
+        match self {
+            PieceColour::White => PieceColour::Black,
+            PieceColour::Black => PieceColour::White,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Piece {
-    // bool is to indicate if this is a target for en passant
-    Pawn(PieceColour, bool),
+    Pawn(PieceColour),
     Knight(PieceColour),
     Bishop(PieceColour),
     Rook(PieceColour),
@@ -32,9 +77,43 @@ pub enum Piece {
     Empty,
 }
 
-fn location(rank: usize, file: usize) -> String {
-    let file = ('a'..='h').into_iter().collect::<Vec<_>>()[file];
-    format!("{}{}", file, rank)
+/// Which castling moves each side has not yet forfeited. Each field holds
+/// the file of the rook involved (classically `7`/h for king-side and
+/// `0`/a for queen-side, but Shredder-FEN/Chess960 positions can put that
+/// rook on any file), or `None` if the right has been forfeited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CastleRights {
+    pub white_king_side: Option<u8>,
+    pub white_queen_side: Option<u8>,
+    pub black_king_side: Option<u8>,
+    pub black_queen_side: Option<u8>,
+}
+
+/// The fully-typed result of parsing a FEN string: the board plus everything
+/// else needed to keep playing a game from this point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Position {
+    pub board: Board,
+    pub side_to_move: PieceColour,
+    pub castle_rights: CastleRights,
+    pub en_passant_target: Option<String>,
+    pub halfmove_clock: u8,
+    pub fullmove_number: u32,
+}
+
+impl Position {
+    /// The `square -> Piece` map the crate used before the bitboard
+    /// representation, for callers that still want it.
+    pub fn board_map(&self) -> HashMap<String, Piece> {
+        self.board.to_map()
+    }
+}
+
+/// FEN lists ranks top (8) to bottom (1); `rank_index` is the position in
+/// that listing (0-7), which this turns into the `Square` the rank/file
+/// actually correspond to on the board.
+fn square_for(rank_index: usize, file_index: usize) -> Square {
+    Square::new((7 - rank_index) as u8, file_index as u8)
 }
 
 fn piece_colour(piece: char) -> PieceColour {
@@ -45,10 +124,10 @@ fn piece_colour(piece: char) -> PieceColour {
     }
 }
 
-fn parse_piece(piece: char, pawn_is_target: bool) -> Result<Piece> {
+fn parse_piece(piece: char) -> Result<Piece> {
     let colour = piece_colour(piece);
     match piece {
-        'P' | 'p' => Ok(Piece::Pawn(colour, pawn_is_target)),
+        'P' | 'p' => Ok(Piece::Pawn(colour)),
         'N' | 'n' => Ok(Piece::Knight(colour)),
         'B' | 'b' => Ok(Piece::Bishop(colour)),
         'R' | 'r' => Ok(Piece::Rook(colour)),
@@ -61,41 +140,580 @@ fn parse_piece(piece: char, pawn_is_target: bool) -> Result<Piece> {
     }
 }
 
-pub fn parse_fen(input: &str) -> Result<HashMap<String, Piece>> {
+fn parse_side_to_move(side: &str) -> Result<PieceColour> {
+    match side {
+        "w" => Ok(PieceColour::White),
+        "b" => Ok(PieceColour::Black),
+        _ => Err(FENParseError::InvalidSideToMove {
+            invalid_side: side.to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Parses the castling section. Besides the classical `KQkq` letters this
+/// also accepts Shredder-FEN/X-FEN notation, where a letter names the file
+/// of the rook the right belongs to (e.g. `A`/`H` for white, `a`/`h` for
+/// black) rather than assuming that rook sits on the a/h file - required
+/// for Chess960 start positions. A letter-named right is resolved against
+/// `board` both to find the right's rook (king-side if it stands east of
+/// the king, queen-side if west) and to reject letters with no rook behind
+/// them; the classical letters are taken at face value here and left for
+/// `validate` to check against the board.
+fn parse_castling_rights(castling: &str, board: &Board) -> Result<CastleRights> {
+    if castling == "-" {
+        return Ok(CastleRights::default());
+    }
+
+    let mut rights = CastleRights::default();
+    for right in castling.chars() {
+        let colour = piece_colour(right);
+        let back_rank = match colour {
+            PieceColour::White => 0,
+            PieceColour::Black => 7,
+        };
+
+        let (file, is_king_side) = match right {
+            'K' => (7, true),
+            'Q' => (0, false),
+            'k' => (7, true),
+            'q' => (0, false),
+            'A'..='H' | 'a'..='h' => {
+                let file = right.to_ascii_uppercase() as u8 - b'A';
+                if !matches!(board.piece_at(Square::new(back_rank, file)), Piece::Rook(rook_colour) if rook_colour == colour)
+                {
+                    return Err(FENParseError::InvalidCastlingRights {
+                        invalid_rights: castling.to_string(),
+                    }
+                    .into());
+                }
+
+                let king_file = match board.king_square(colour) {
+                    Some(square) if square.rank() == back_rank => square.file(),
+                    _ => {
+                        return Err(FENParseError::InvalidCastlingRights {
+                            invalid_rights: castling.to_string(),
+                        }
+                        .into())
+                    }
+                };
+
+                (file, file > king_file)
+            }
+            _ => {
+                return Err(FENParseError::InvalidCastlingRights {
+                    invalid_rights: castling.to_string(),
+                }
+                .into())
+            }
+        };
+
+        match (colour, is_king_side) {
+            (PieceColour::White, true) => rights.white_king_side = Some(file),
+            (PieceColour::White, false) => rights.white_queen_side = Some(file),
+            (PieceColour::Black, true) => rights.black_king_side = Some(file),
+            (PieceColour::Black, false) => rights.black_queen_side = Some(file),
+        }
+    }
+
+    Ok(rights)
+}
+
+fn parse_en_passant_target(en_passant_target: &str) -> Option<String> {
+    if en_passant_target == "-" {
+        None
+    } else {
+        Some(en_passant_target.to_string())
+    }
+}
+
+fn parse_halfmove_clock(halfmove: &str) -> Result<u8> {
+    halfmove.parse::<u8>().map_err(|_| {
+        FENParseError::InvalidClock {
+            invalid_clock: halfmove.to_string(),
+        }
+        .into()
+    })
+}
+
+fn parse_fullmove_number(fullmove: &str) -> Result<u32> {
+    fullmove.parse::<u32>().map_err(|_| {
+        FENParseError::InvalidClock {
+            invalid_clock: fullmove.to_string(),
+        }
+        .into()
+    })
+}
+
+fn piece_to_char(piece: Piece) -> char {
+    let (letter, colour) = match piece {
+        Piece::Pawn(colour) => ('p', colour),
+        Piece::Knight(colour) => ('n', colour),
+        Piece::Bishop(colour) => ('b', colour),
+        Piece::Rook(colour) => ('r', colour),
+        Piece::Queen(colour) => ('q', colour),
+        Piece::King(colour) => ('k', colour),
+        Piece::Empty => unreachable!("empty squares are never serialized as a letter"),
+    };
+
+    match colour {
+        PieceColour::White => letter.to_ascii_uppercase(),
+        PieceColour::Black => letter,
+    }
+}
+
+fn side_to_move_to_str(side: PieceColour) -> &'static str {
+    match side {
+        PieceColour::White => "w",
+        PieceColour::Black => "b",
+    }
+}
+
+/// The letter for a single right: its classical letter when the rook sits
+/// on the classical file (a/h), or the Shredder file letter otherwise.
+fn castle_right_letter(file: u8, classical_file: u8, classical: char, shredder_base: u8) -> char {
+    if file == classical_file {
+        classical
+    } else {
+        (shredder_base + file) as char
+    }
+}
+
+/// Appends one colour's castling letters. When every right uses the
+/// classical a/h rook file they keep the classical king-side-then-queen-side
+/// `KQ` order; otherwise they are ordered by rook file ascending, since the
+/// fixed `KQ` order is backwards whenever the queen-side rook (always the
+/// lower file) has a non-classical file.
+fn push_side_rights(
+    result: &mut String,
+    king_side: Option<u8>,
+    queen_side: Option<u8>,
+    classical: (char, char),
+    shredder_base: u8,
+) {
+    let (king_letter, queen_letter) = classical;
+    let mut rights: Vec<(u8, char)> = Vec::new();
+    if let Some(file) = king_side {
+        rights.push((file, castle_right_letter(file, 7, king_letter, shredder_base)));
+    }
+    if let Some(file) = queen_side {
+        rights.push((file, castle_right_letter(file, 0, queen_letter, shredder_base)));
+    }
+
+    let all_classical =
+        king_side.map_or(true, |file| file == 7) && queen_side.map_or(true, |file| file == 0);
+    if !all_classical {
+        rights.sort_by_key(|&(file, _)| file);
+    }
+    for (_, letter) in rights {
+        result.push(letter);
+    }
+}
+
+fn castle_rights_to_string(rights: &CastleRights) -> String {
+    let mut result = String::new();
+    push_side_rights(
+        &mut result,
+        rights.white_king_side,
+        rights.white_queen_side,
+        ('K', 'Q'),
+        b'A',
+    );
+    push_side_rights(
+        &mut result,
+        rights.black_king_side,
+        rights.black_queen_side,
+        ('k', 'q'),
+        b'a',
+    );
+
+    if result.is_empty() {
+        result.push('-');
+    }
+
+    result
+}
+
+pub fn parse_fen(input: &str) -> Result<Position> {
     let pieces = input.split(' ').collect::<Vec<_>>();
-    let ranks = pieces[0].split('/');
-    let ranks_with_index = ranks.enumerate();
-    let [_, side, castling, en_passant_target, halfmove, fullmove] = pieces[..] else {
+    let [placement, side, castling, en_passant_target, halfmove, fullmove] = pieces[..] else {
         return Err(FENParseError::InvalidSectionCount {
             count: pieces.len(),
         }
         .into());
     };
 
-    let mut expanded_ranks = HashMap::new();
-    for (rank_index, rank) in ranks_with_index {
+    let ranks: Vec<&str> = placement.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(FENParseError::InvalidRankCount { count: ranks.len() }.into());
+    }
+
+    let mut board = Board::empty();
+    for (rank_index, rank) in ranks.into_iter().enumerate() {
         let mut file_index: usize = 0;
         for piece in rank.chars() {
             match piece {
-                '1'..'8' => {
-                    file_index = file_index
-                        + piece
-                            .to_digit(10)
-                            .expect("Digit case didn't produce a digit")
-                            as usize
+                '1'..='8' => {
+                    file_index += piece
+                        .to_digit(10)
+                        .expect("Digit case didn't produce a digit")
+                        as usize;
+                    if file_index > 8 {
+                        return Err(FENParseError::InvalidRankLength {
+                            rank: rank.to_string(),
+                        }
+                        .into());
+                    }
                 }
                 _ => {
-                    let result = expanded_ranks.insert(
-                        location(rank_index, file_index),
-                        parse_piece(piece, en_passant_target == location(rank_index, file_index))?,
-                    );
-                    if let Some(_) = result {
-                        return Err(FENParseError::DuplicateSquare.into())
+                    if file_index >= 8 {
+                        return Err(FENParseError::InvalidRankLength {
+                            rank: rank.to_string(),
+                        }
+                        .into());
+                    }
+
+                    let square = square_for(rank_index, file_index);
+                    if !matches!(board.piece_at(square), Piece::Empty) {
+                        return Err(FENParseError::DuplicateSquare.into());
                     }
+
+                    board.set(square, parse_piece(piece)?);
+                    file_index += 1;
                 }
             };
         }
+
+        if file_index != 8 {
+            return Err(FENParseError::InvalidRankLength {
+                rank: rank.to_string(),
+            }
+            .into());
+        }
     }
 
-    Ok(expanded_ranks)
+    let castle_rights = parse_castling_rights(castling, &board)?;
+
+    Ok(Position {
+        board,
+        side_to_move: parse_side_to_move(side)?,
+        castle_rights,
+        en_passant_target: parse_en_passant_target(en_passant_target),
+        halfmove_clock: parse_halfmove_clock(halfmove)?,
+        fullmove_number: parse_fullmove_number(fullmove)?,
+    })
+}
+
+impl Position {
+    /// Reconstruct a canonical FEN string for this position. Round-trips
+    /// with `parse_fen`: `parse_fen(position.to_fen())? == position`.
+    pub fn to_fen(&self) -> String {
+        let mut ranks = Vec::with_capacity(8);
+        for rank_index in 0..8 {
+            let mut rank = String::new();
+            let mut empty_run = 0;
+            for file_index in 0..8 {
+                let piece = self.board.piece_at(square_for(rank_index, file_index));
+                if matches!(piece, Piece::Empty) {
+                    empty_run += 1;
+                    continue;
+                }
+
+                if empty_run > 0 {
+                    rank.push_str(&empty_run.to_string());
+                    empty_run = 0;
+                }
+                rank.push(piece_to_char(piece));
+            }
+            if empty_run > 0 {
+                rank.push_str(&empty_run.to_string());
+            }
+            ranks.push(rank);
+        }
+
+        format!(
+            "{} {} {} {} {} {}",
+            ranks.join("/"),
+            side_to_move_to_str(self.side_to_move),
+            castle_rights_to_string(&self.castle_rights),
+            self.en_passant_target.as_deref().unwrap_or("-"),
+            self.halfmove_clock,
+            self.fullmove_number,
+        )
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_fen())
+    }
+}
+
+fn validate_kings(position: &Position) -> Result<(), InvalidError> {
+    let board = &position.board;
+
+    let white_count = board.kings(PieceColour::White).count_ones() as usize;
+    if white_count != 1 {
+        return Err(InvalidError::WrongKingCount {
+            colour: PieceColour::White,
+            count: white_count,
+        });
+    }
+    let black_count = board.kings(PieceColour::Black).count_ones() as usize;
+    if black_count != 1 {
+        return Err(InvalidError::WrongKingCount {
+            colour: PieceColour::Black,
+            count: black_count,
+        });
+    }
+
+    let white_king = board.king_square(PieceColour::White).expect("checked above");
+    let black_king = board.king_square(PieceColour::Black).expect("checked above");
+    if white_king.rank().abs_diff(black_king.rank()) <= 1
+        && white_king.file().abs_diff(black_king.file()) <= 1
+    {
+        return Err(InvalidError::NeighbouringKings);
+    }
+
+    Ok(())
+}
+
+fn validate_pawns(position: &Position) -> Result<(), InvalidError> {
+    for file in 0..8 {
+        for rank in [0, 7] {
+            if let Piece::Pawn(_) = position.board.piece_at(Square::new(rank, file)) {
+                return Err(InvalidError::PawnOnBackRank);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks one right against the board: the king must stand on its back
+/// rank, and the right's rook file must hold a rook of the same colour.
+/// Unlike the classical a/h files, a Chess960 king isn't tied to the
+/// e-file, so this doesn't assume where on the back rank it sits.
+fn check_castle_right(
+    board: &Board,
+    right: Option<u8>,
+    colour: PieceColour,
+    back_rank: u8,
+    label: &str,
+) -> Result<(), InvalidError> {
+    let Some(file) = right else {
+        return Ok(());
+    };
+
+    let has_king = board
+        .king_square(colour)
+        .is_some_and(|square| square.rank() == back_rank);
+    let has_rook = matches!(board.piece_at(Square::new(back_rank, file)), Piece::Rook(rook_colour) if rook_colour == colour);
+
+    if has_king && has_rook {
+        Ok(())
+    } else {
+        Err(InvalidError::InvalidCastlingRights {
+            reason: format!(
+                "{label} rights require a {colour:?} king on the back rank and a {colour:?} rook on {}{}",
+                (b'a' + file) as char,
+                back_rank + 1,
+            ),
+        })
+    }
+}
+
+fn validate_castle_rights(position: &Position) -> Result<(), InvalidError> {
+    let rights = &position.castle_rights;
+    let board = &position.board;
+
+    check_castle_right(board, rights.white_king_side, PieceColour::White, 0, "white king-side")?;
+    check_castle_right(board, rights.white_queen_side, PieceColour::White, 0, "white queen-side")?;
+    check_castle_right(board, rights.black_king_side, PieceColour::Black, 7, "black king-side")?;
+    check_castle_right(board, rights.black_queen_side, PieceColour::Black, 7, "black queen-side")?;
+
+    Ok(())
+}
+
+fn validate_en_passant(position: &Position) -> Result<(), InvalidError> {
+    let Some(target) = &position.en_passant_target else {
+        return Ok(());
+    };
+
+    let target_square = Square::from_algebraic(target).ok_or_else(|| InvalidError::InvalidEnPassant {
+        reason: format!("{:?} is not a valid square", target),
+    })?;
+
+    if !matches!(position.board.piece_at(target_square), Piece::Empty) {
+        return Err(InvalidError::InvalidEnPassant {
+            reason: format!("{} is occupied", target),
+        });
+    }
+
+    // The pawn that just made the double step is the opposite colour to
+    // whoever is now to move, and sits one rank further along its own
+    // advance than the target square it passed over.
+    let (expected_rank, pawn_rank, pawn_colour) = match position.side_to_move {
+        PieceColour::Black => (2, 3, PieceColour::White),
+        PieceColour::White => (5, 4, PieceColour::Black),
+    };
+
+    if target_square.rank() != expected_rank {
+        return Err(InvalidError::InvalidEnPassant {
+            reason: format!(
+                "{} is not on the rank a double pawn step could land behind",
+                target
+            ),
+        });
+    }
+
+    let pawn_square = Square::new(pawn_rank, target_square.file());
+    match position.board.piece_at(pawn_square) {
+        Piece::Pawn(colour) if colour == pawn_colour => Ok(()),
+        _ => Err(InvalidError::InvalidEnPassant {
+            reason: format!("no {:?} pawn behind {}", pawn_colour, target),
+        }),
+    }
+}
+
+/// Rejects positions that could never arise from a legal game, the way a
+/// `parse_fen` caller who cares about strict validity would want. Run this
+/// as an optional pass after parsing.
+pub fn validate(position: &Position) -> Result<(), InvalidError> {
+    validate_kings(position)?;
+    validate_pawns(position)?;
+    validate_castle_rights(position)?;
+    validate_en_passant(position)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trips(fen: &str) {
+        let position = parse_fen(fen).unwrap();
+        let round_tripped = parse_fen(&position.to_fen()).unwrap();
+        assert_eq!(position, round_tripped);
+    }
+
+    #[test]
+    fn round_trips_starting_position() {
+        round_trips("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    }
+
+    #[test]
+    fn round_trips_midgame_position_with_en_passant() {
+        round_trips("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3");
+    }
+
+    #[test]
+    fn round_trips_position_with_no_castling_rights() {
+        round_trips("8/8/8/4k3/8/8/8/4K3 b - - 12 34");
+    }
+
+    #[test]
+    fn validates_starting_position() {
+        let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        assert!(validate(&position).is_ok());
+    }
+
+    #[test]
+    fn validates_en_passant_position() {
+        let position = parse_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3").unwrap();
+        assert!(validate(&position).is_ok());
+    }
+
+    #[test]
+    fn rejects_missing_king() {
+        let position = parse_fen("rnbqbbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        assert_eq!(
+            validate(&position),
+            Err(InvalidError::WrongKingCount {
+                colour: PieceColour::Black,
+                count: 0
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_pawn_on_back_rank() {
+        let position = parse_fen("rnbqkbnP/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        assert_eq!(validate(&position), Err(InvalidError::PawnOnBackRank));
+    }
+
+    #[test]
+    fn rejects_neighbouring_kings() {
+        let position = parse_fen("8/8/8/3kK3/8/8/8/8 w - - 0 1").unwrap();
+        assert_eq!(validate(&position), Err(InvalidError::NeighbouringKings));
+    }
+
+    #[test]
+    fn parses_shredder_style_castling_rights() {
+        let position =
+            parse_fen("nrkqbbnr/pppppppp/8/8/8/8/PPPPPPPP/NRKQBBNR w BHbh - 0 1").unwrap();
+        assert_eq!(position.castle_rights.white_queen_side, Some(1));
+        assert_eq!(position.castle_rights.white_king_side, Some(7));
+        assert_eq!(position.castle_rights.black_queen_side, Some(1));
+        assert_eq!(position.castle_rights.black_king_side, Some(7));
+    }
+
+    #[test]
+    fn round_trips_shredder_style_castling_rights() {
+        round_trips("nrkqbbnr/pppppppp/8/8/8/8/PPPPPPPP/NRKQBBNR w BHbh - 0 1");
+    }
+
+    #[test]
+    fn shredder_castling_letters_are_ordered_by_rook_file() {
+        let position =
+            parse_fen("nrkqbbrn/pppppppp/8/8/8/8/PPPPPPPP/NRKQBBRN w BGbg - 0 1").unwrap();
+        let fen = position.to_fen();
+        let castling = fen.split(' ').nth(2).unwrap();
+        assert_eq!(castling, "BGbg");
+    }
+
+    #[test]
+    fn rejects_shredder_castling_letter_without_a_rook() {
+        assert!(parse_fen("nrkqbbnr/pppppppp/8/8/8/8/PPPPPPPP/NRKQBBNR w CHbh - 0 1").is_err());
+    }
+
+    #[test]
+    fn rejects_castling_rights_without_rook() {
+        let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBN1 w KQkq - 0 1").unwrap();
+        assert!(matches!(
+            validate(&position),
+            Err(InvalidError::InvalidCastlingRights { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_en_passant_without_double_stepped_pawn() {
+        let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e6 0 1").unwrap();
+        assert!(matches!(
+            validate(&position),
+            Err(InvalidError::InvalidEnPassant { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_a_rank_with_too_many_files() {
+        assert!(matches!(
+            parse_fen("ppppppppp/8/8/8/8/8/8/8 w - - 0 1"),
+            Err(err) if matches!(
+                err.downcast_ref::<FENParseError>(),
+                Some(FENParseError::InvalidRankLength { .. })
+            )
+        ));
+    }
+
+    #[test]
+    fn rejects_too_many_ranks() {
+        assert!(matches!(
+            parse_fen("8/8/8/8/8/8/8/8/p w - - 0 1"),
+            Err(err) if matches!(
+                err.downcast_ref::<FENParseError>(),
+                Some(FENParseError::InvalidRankCount { .. })
+            )
+        ));
+    }
 }
@@ -0,0 +1,4 @@
+pub mod board;
+pub mod fen;
+pub mod movegen;
+pub mod render;
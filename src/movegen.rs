@@ -0,0 +1,395 @@
+use std::ops::RangeInclusive;
+
+use crate::board::{bishop_attacks, king_attacks, knight_attacks, rook_attacks, Board, Square};
+use crate::fen::{Piece, PieceColour, Position};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromotionPiece {
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+}
+
+/// Move effects that are more than "a piece moved from one square to
+/// another" - the board needs extra bookkeeping beyond `from`/`to`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SideEffect {
+    EnPassantCapture,
+    /// A castle, carrying the rook's own from/to squares since Chess960
+    /// positions can start that rook (and the king) on any back-rank file.
+    Castle { rook_from: Square, rook_to: Square },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<PromotionPiece>,
+    pub side_effect: Option<SideEffect>,
+}
+
+impl Move {
+    fn quiet(from: Square, to: Square) -> Self {
+        Move {
+            from,
+            to,
+            promotion: None,
+            side_effect: None,
+        }
+    }
+}
+
+const PROMOTION_PIECES: [PromotionPiece; 4] = [
+    PromotionPiece::Queen,
+    PromotionPiece::Rook,
+    PromotionPiece::Bishop,
+    PromotionPiece::Knight,
+];
+
+/// All legal moves for `position.side_to_move`: every pseudo-legal move,
+/// minus those that would leave that side's own king in check.
+pub fn generate_legal_moves(position: &Position) -> Vec<Move> {
+    generate_pseudo_legal_moves(position)
+        .into_iter()
+        .filter(|mv| is_legal(position, mv))
+        .collect()
+}
+
+fn generate_pseudo_legal_moves(position: &Position) -> Vec<Move> {
+    let colour = position.side_to_move;
+    let board = &position.board;
+    let own_occupancy = board.occupancy_for(colour);
+    let occupancy = board.occupancy();
+
+    let mut moves = Vec::new();
+
+    for index in 0..64u8 {
+        let square = Square(index);
+        match board.piece_at(square) {
+            Piece::Pawn(piece_colour) if piece_colour == colour => {
+                generate_pawn_moves(position, square, &mut moves)
+            }
+            Piece::Knight(piece_colour) if piece_colour == colour => {
+                add_targets(square, knight_attacks(square) & !own_occupancy, &mut moves)
+            }
+            Piece::Bishop(piece_colour) if piece_colour == colour => add_targets(
+                square,
+                bishop_attacks(square, occupancy) & !own_occupancy,
+                &mut moves,
+            ),
+            Piece::Rook(piece_colour) if piece_colour == colour => add_targets(
+                square,
+                rook_attacks(square, occupancy) & !own_occupancy,
+                &mut moves,
+            ),
+            Piece::Queen(piece_colour) if piece_colour == colour => {
+                let attacks = bishop_attacks(square, occupancy) | rook_attacks(square, occupancy);
+                add_targets(square, attacks & !own_occupancy, &mut moves)
+            }
+            Piece::King(piece_colour) if piece_colour == colour => {
+                add_targets(square, king_attacks(square) & !own_occupancy, &mut moves);
+            }
+            _ => {}
+        }
+    }
+
+    generate_castle_moves(position, &mut moves);
+
+    moves
+}
+
+fn add_targets(from: Square, targets: u64, moves: &mut Vec<Move>) {
+    for index in 0..64u8 {
+        if targets & (1u64 << index) != 0 {
+            moves.push(Move::quiet(from, Square(index)));
+        }
+    }
+}
+
+fn generate_pawn_moves(position: &Position, from: Square, moves: &mut Vec<Move>) {
+    let board = &position.board;
+    let colour = position.side_to_move;
+    let occupancy = board.occupancy();
+    let enemy_occupancy = board.occupancy_for(colour.opposite());
+
+    let (direction, start_rank, promotion_rank): (i8, u8, u8) = match colour {
+        PieceColour::White => (1, 1, 7),
+        PieceColour::Black => (-1, 6, 0),
+    };
+
+    let rank = from.rank() as i8;
+    let file = from.file() as i8;
+
+    let single_rank = rank + direction;
+    if (0..8).contains(&single_rank) {
+        let single_target = Square::new(single_rank as u8, file as u8);
+        if occupancy & (1u64 << single_target.0) == 0 {
+            push_pawn_move(from, single_target, promotion_rank, moves);
+
+            if from.rank() == start_rank {
+                let double_target = Square::new((rank + direction * 2) as u8, file as u8);
+                if occupancy & (1u64 << double_target.0) == 0 {
+                    moves.push(Move::quiet(from, double_target));
+                }
+            }
+        }
+    }
+
+    for df in [-1, 1] {
+        let target_rank = rank + direction;
+        let target_file = file + df;
+        if !(0..8).contains(&target_rank) || !(0..8).contains(&target_file) {
+            continue;
+        }
+
+        let target = Square::new(target_rank as u8, target_file as u8);
+        let bit = 1u64 << target.0;
+
+        if enemy_occupancy & bit != 0 {
+            push_pawn_move(from, target, promotion_rank, moves);
+        } else if position.en_passant_target.as_deref() == Some(target.to_algebraic().as_str()) {
+            moves.push(Move {
+                from,
+                to: target,
+                promotion: None,
+                side_effect: Some(SideEffect::EnPassantCapture),
+            });
+        }
+    }
+}
+
+fn push_pawn_move(from: Square, to: Square, promotion_rank: u8, moves: &mut Vec<Move>) {
+    if to.rank() == promotion_rank {
+        for &promotion in &PROMOTION_PIECES {
+            moves.push(Move {
+                from,
+                to,
+                promotion: Some(promotion),
+                side_effect: None,
+            });
+        }
+    } else {
+        moves.push(Move::quiet(from, to));
+    }
+}
+
+/// The files from `from` to `to` inclusive, in whichever direction they
+/// happen to be ordered - castling paths can run either way across the
+/// board depending on where Chess960 puts the king and rook.
+fn files_between(from: u8, to: u8) -> RangeInclusive<u8> {
+    if from <= to {
+        from..=to
+    } else {
+        to..=from
+    }
+}
+
+/// Whether every square `from`..=`to` on `back_rank` is empty, other than
+/// the squares the castling king and rook themselves start on.
+fn castle_path_clear(board: &Board, back_rank: u8, from: u8, to: u8, castlers: [Square; 2]) -> bool {
+    let occupancy = board.occupancy();
+    files_between(from, to).all(|file| {
+        let square = Square::new(back_rank, file);
+        castlers.contains(&square) || occupancy & (1u64 << square.0) == 0
+    })
+}
+
+/// Whether the king can pass through every square `from`..=`to` on
+/// `back_rank` without ever standing on an attacked square.
+fn castle_king_path_safe(board: &Board, back_rank: u8, from: u8, to: u8, enemy: PieceColour) -> bool {
+    files_between(from, to).all(|file| !board.is_attacked(Square::new(back_rank, file), enemy))
+}
+
+/// Attempts the castle that moves the king from `king_square` to file
+/// `king_target_file` and the rook standing on `rook_file` to file
+/// `rook_target_file`, pushing the move if both pieces' paths are clear
+/// and the king never crosses an attacked square.
+#[allow(clippy::too_many_arguments)]
+fn try_generate_castle_move(
+    board: &Board,
+    king_square: Square,
+    rook_file: u8,
+    back_rank: u8,
+    king_target_file: u8,
+    rook_target_file: u8,
+    enemy: PieceColour,
+    moves: &mut Vec<Move>,
+) {
+    let rook_square = Square::new(back_rank, rook_file);
+    let castlers = [king_square, rook_square];
+
+    if !castle_path_clear(board, back_rank, king_square.file(), king_target_file, castlers)
+        || !castle_path_clear(board, back_rank, rook_file, rook_target_file, castlers)
+    {
+        return;
+    }
+
+    if !castle_king_path_safe(board, back_rank, king_square.file(), king_target_file, enemy) {
+        return;
+    }
+
+    moves.push(Move {
+        from: king_square,
+        to: Square::new(back_rank, king_target_file),
+        promotion: None,
+        side_effect: Some(SideEffect::Castle {
+            rook_from: rook_square,
+            rook_to: Square::new(back_rank, rook_target_file),
+        }),
+    });
+}
+
+fn generate_castle_moves(position: &Position, moves: &mut Vec<Move>) {
+    let colour = position.side_to_move;
+    let board = &position.board;
+
+    let Some(king_square) = board.king_square(colour) else {
+        return;
+    };
+    let back_rank = match colour {
+        PieceColour::White => 0,
+        PieceColour::Black => 7,
+    };
+    if king_square.rank() != back_rank {
+        return;
+    }
+
+    let enemy = colour.opposite();
+    let rights = &position.castle_rights;
+    let (king_side_right, queen_side_right) = match colour {
+        PieceColour::White => (rights.white_king_side, rights.white_queen_side),
+        PieceColour::Black => (rights.black_king_side, rights.black_queen_side),
+    };
+
+    if let Some(rook_file) = king_side_right {
+        try_generate_castle_move(board, king_square, rook_file, back_rank, 6, 5, enemy, moves);
+    }
+    if let Some(rook_file) = queen_side_right {
+        try_generate_castle_move(board, king_square, rook_file, back_rank, 2, 3, enemy, moves);
+    }
+}
+
+fn is_legal(position: &Position, mv: &Move) -> bool {
+    let colour = position.side_to_move;
+    let board_after = board_after_move(&position.board, mv);
+
+    match board_after.king_square(colour) {
+        Some(square) => !board_after.is_attacked(square, colour.opposite()),
+        None => false,
+    }
+}
+
+/// Applies `mv` to a copy of `board`, purely to test whether the mover's
+/// king would be left in check - it doesn't touch castling rights, the
+/// halfmove clock, or anything else tracked on `Position`.
+fn board_after_move(board: &Board, mv: &Move) -> Board {
+    let mut board = board.clone();
+    let moving_piece = board.piece_at(mv.from);
+    board.clear(mv.from);
+
+    if let Some(SideEffect::EnPassantCapture) = mv.side_effect {
+        let captured_square = Square::new(mv.from.rank(), mv.to.file());
+        board.clear(captured_square);
+    }
+
+    // The rook moves before the king lands, since a Chess960 king can land
+    // on the square its rook starts on.
+    if let Some(SideEffect::Castle { rook_from, rook_to }) = mv.side_effect {
+        move_rook(&mut board, rook_from, rook_to);
+    }
+
+    let placed_piece = match mv.promotion {
+        Some(promotion) => promote(moving_piece, promotion),
+        None => moving_piece,
+    };
+    board.set(mv.to, placed_piece);
+
+    board
+}
+
+fn move_rook(board: &mut Board, from: Square, to: Square) {
+    let rook = board.piece_at(from);
+    board.clear(from);
+    board.set(to, rook);
+}
+
+fn promote(piece: Piece, promotion: PromotionPiece) -> Piece {
+    let colour = match piece {
+        Piece::Pawn(colour) => colour,
+        _ => unreachable!("only pawns promote"),
+    };
+
+    match promotion {
+        PromotionPiece::Knight => Piece::Knight(colour),
+        PromotionPiece::Bishop => Piece::Bishop(colour),
+        PromotionPiece::Rook => Piece::Rook(colour),
+        PromotionPiece::Queen => Piece::Queen(colour),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fen::parse_fen;
+
+    #[test]
+    fn starting_position_has_twenty_legal_moves() {
+        let position =
+            parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        assert_eq!(generate_legal_moves(&position).len(), 20);
+    }
+
+    #[test]
+    fn king_in_check_must_escape_the_checking_file() {
+        // The white king on e1 is checked by the black rook on e8 with
+        // nothing in between: only the four king moves that step off the
+        // e-file are legal (e2 is still on it, so it stays illegal too).
+        let position = parse_fen("4r3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let moves = generate_legal_moves(&position);
+        assert_eq!(moves.len(), 4);
+        assert!(moves.iter().all(|mv| mv.to.file() != 4));
+    }
+
+    #[test]
+    fn en_passant_capture_is_generated() {
+        let position =
+            parse_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3").unwrap();
+        let moves = generate_legal_moves(&position);
+        assert!(moves
+            .iter()
+            .any(|mv| mv.side_effect == Some(SideEffect::EnPassantCapture)));
+    }
+
+    #[test]
+    fn castling_is_generated_when_path_is_clear_and_safe() {
+        let position = parse_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        let moves = generate_legal_moves(&position);
+        assert!(moves.iter().any(|mv| mv.side_effect
+            == Some(SideEffect::Castle {
+                rook_from: Square::new(0, 7),
+                rook_to: Square::new(0, 5),
+            })));
+        assert!(moves.iter().any(|mv| mv.side_effect
+            == Some(SideEffect::Castle {
+                rook_from: Square::new(0, 0),
+                rook_to: Square::new(0, 3),
+            })));
+    }
+
+    #[test]
+    fn chess960_king_starting_off_the_e_file_still_castles() {
+        // The king starts on b1 rather than e1, so queen-side castling only
+        // steps it one square over - the fixed e1 assumption the classical
+        // generator used to make would have missed this entirely.
+        let position = parse_fen("4k3/8/8/8/8/8/8/RK6 w Q - 0 1").unwrap();
+        let moves = generate_legal_moves(&position);
+        assert!(moves.iter().any(|mv| mv.from == Square::new(0, 1)
+            && mv.to == Square::new(0, 2)
+            && mv.side_effect
+                == Some(SideEffect::Castle {
+                    rook_from: Square::new(0, 0),
+                    rook_to: Square::new(0, 3),
+                })));
+    }
+}
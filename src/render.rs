@@ -0,0 +1,90 @@
+use crate::board::Square;
+use crate::fen::{Piece, PieceColour, Position};
+
+/// Which side's back rank is printed at the bottom of the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    WhiteAtBottom,
+    BlackAtBottom,
+}
+
+fn glyph(piece: Piece) -> char {
+    match piece {
+        Piece::Pawn(PieceColour::White) => '♙',
+        Piece::Knight(PieceColour::White) => '♘',
+        Piece::Bishop(PieceColour::White) => '♗',
+        Piece::Rook(PieceColour::White) => '♖',
+        Piece::Queen(PieceColour::White) => '♕',
+        Piece::King(PieceColour::White) => '♔',
+        Piece::Pawn(PieceColour::Black) => '♟',
+        Piece::Knight(PieceColour::Black) => '♞',
+        Piece::Bishop(PieceColour::Black) => '♝',
+        Piece::Rook(PieceColour::Black) => '♜',
+        Piece::Queen(PieceColour::Black) => '♛',
+        Piece::King(PieceColour::Black) => '♚',
+        Piece::Empty => '.',
+    }
+}
+
+/// Draws an 8x8 grid of Unicode chess glyphs with rank numbers down the
+/// side and file letters underneath, oriented however the caller likes.
+pub fn render(position: &Position, orientation: Orientation) -> String {
+    let ranks: Box<dyn Iterator<Item = u8>> = match orientation {
+        Orientation::WhiteAtBottom => Box::new((0..8).rev()),
+        Orientation::BlackAtBottom => Box::new(0..8),
+    };
+    let files: Vec<u8> = match orientation {
+        Orientation::WhiteAtBottom => (0..8).collect(),
+        Orientation::BlackAtBottom => (0..8).rev().collect(),
+    };
+
+    let mut output = String::new();
+    for rank in ranks {
+        output.push_str(&format!("{} ", rank + 1));
+        for &file in &files {
+            let piece = position.board.piece_at(Square::new(rank, file));
+            output.push(glyph(piece));
+            output.push(' ');
+        }
+        output.push('\n');
+    }
+
+    output.push_str("  ");
+    for &file in &files {
+        output.push((b'a' + file) as char);
+        output.push(' ');
+    }
+    output.push('\n');
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fen::parse_fen;
+
+    #[test]
+    fn renders_rank_one_at_the_bottom_when_white_is_at_bottom() {
+        let position =
+            parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let rendered = render(&position, Orientation::WhiteAtBottom);
+
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[0].starts_with("8 "));
+        assert!(lines[7].starts_with("1 "));
+        assert!(rendered.contains('♔'));
+        assert!(rendered.contains('♚'));
+    }
+
+    #[test]
+    fn flipping_orientation_puts_black_at_the_bottom() {
+        let position =
+            parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let rendered = render(&position, Orientation::BlackAtBottom);
+
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[0].starts_with("1 "));
+        assert!(lines[7].starts_with("8 "));
+    }
+}